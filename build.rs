@@ -0,0 +1,80 @@
+use std::{collections::HashSet, env, fs, path::Path};
+
+struct RawWord {
+    string: String,
+    display: String,
+}
+
+fn normalize(word: &str) -> String {
+    unidecode::unidecode(word).to_lowercase()
+}
+
+fn string_sum(string: &str) -> i32 {
+    string.as_bytes().iter().map(|&b| b as i32).sum()
+}
+
+fn load_and_preprocess(path: &str) -> Vec<RawWord> {
+    let raw = fs::read_to_string(path).expect("could not read bundled word list");
+
+    let mut seen = HashSet::new();
+    let mut words: Vec<RawWord> = raw
+        .lines()
+        .filter_map(|display| {
+            let string = normalize(display);
+            if seen.insert(string.clone()) {
+                Some(RawWord { string, display: display.to_string() })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    words.sort_by(|a, b| {
+        if a.string.len() == b.string.len() {
+            string_sum(&a.string).cmp(&string_sum(&b.string))
+        } else {
+            a.string.len().cmp(&b.string.len())
+        }
+    });
+
+    words
+}
+
+fn emit_word_table(words: &[RawWord], dest: &Path) {
+    let mut out = String::from("pub(crate) static EMBEDDED_WORDS: &[(&str, &str)] = &[\n");
+    for word in words {
+        out.push_str(&format!("    ({:?}, {:?}),\n", word.string, word.display));
+    }
+    out.push_str("];\n");
+    fs::write(dest, out).expect("could not write generated word table");
+}
+
+/// Selects the bundled dictionary for the embedded build based on which
+/// `lang-*` feature is enabled, mirroring the per-language feature flags in
+/// Cargo.toml. Defaults to English when no language feature is selected.
+fn dictionary_path() -> &'static str {
+    if env::var_os("CARGO_FEATURE_LANG_FR").is_some() {
+        "dictionaries/mots_francais.txt"
+    } else {
+        "dictionaries/alpha_words.txt"
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // Only the `embedded` feature needs a baked-in dictionary; every other
+    // build must not read or depend on the bundled word lists.
+    if env::var_os("CARGO_FEATURE_EMBEDDED").is_none() {
+        return;
+    }
+
+    let dict_path = dictionary_path();
+    println!("cargo:rerun-if-changed={}", dict_path);
+
+    let words = load_and_preprocess(dict_path);
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let dest = Path::new(&out_dir).join("embedded_words.rs");
+    emit_word_table(&words, &dest);
+}
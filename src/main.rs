@@ -4,20 +4,33 @@ use std::{
     fs::File,
     io::{prelude::*, BufReader, Write, stdout},
     path::Path,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     time::Instant
 };
 
 use core_simd::*;
+use unidecode::unidecode;
 
-struct Word {
-    string: String,
-    string_sum: i32,
-    char_tbl: [i8; 256]
+mod phrase;
+mod segment;
+mod trie;
+
+#[cfg(feature = "embedded")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded_words.rs"));
+}
+
+pub(crate) struct Word {
+    // Lowercased, transliterated form, used for comparisons.
+    pub(crate) string: String,
+    // Original spelling, used for display only.
+    pub(crate) display: String,
+    pub(crate) string_sum: i32,
+    pub(crate) char_tbl: [i8; 256]
 }
 
-struct WordList {
-    words: Vec<Word>,
+pub(crate) struct WordList {
+    pub(crate) words: Vec<Word>,
 }
 
 struct WordListIter<'a> {
@@ -28,25 +41,68 @@ struct WordListIter<'a> {
 
 impl WordList {
     fn from_file(path: &str) -> Self {
-        let mut raw_lines = lines_from_file(path);
-        raw_lines.sort_by(|a, b| {
+        let raw_lines = lines_from_file(path);
+
+        let mut seen = HashSet::new();
+        let mut normalized: Vec<(String, String)> = raw_lines
+            .into_iter()
+            .filter_map(|display| {
+                let string = unidecode(&display).to_lowercase();
+                if seen.insert(string.clone()) {
+                    Some((string, display))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        normalized.sort_by(|(a, _), (b, _)| {
             if a.len() == b.len() {
                 Word::_string_sum(a).cmp(&Word::_string_sum(b))
             } else {
                 a.len().cmp(&b.len())
             }
         });
-        let lines = raw_lines.into_iter().map(|s| Word::new(s)).collect::<Vec<Word>>();
+
+        let words = normalized
+            .into_iter()
+            .map(|(string, display)| Word::new(string, display))
+            .collect::<Vec<Word>>();
 
         Self {
-            words: lines,
+            words,
         }
     }
 
+    /// Builds a `WordList` from the dictionary baked into the binary at
+    /// compile time by `build.rs`, with zero runtime parsing or file access.
+    #[cfg(feature = "embedded")]
+    fn from_embedded() -> Self {
+        let words = embedded::EMBEDDED_WORDS
+            .iter()
+            .map(|&(string, display)| Word::new(string.to_string(), display.to_string()))
+            .collect();
+
+        Self { words }
+    }
+
     fn len(&self) -> usize {
         self.words.len()
     }
 
+    /// Loads the vocabulary: from the binary's embedded dictionary when
+    /// built with `--features embedded`, otherwise from the bundled file.
+    fn load_default() -> Self {
+        #[cfg(feature = "embedded")]
+        {
+            Self::from_embedded()
+        }
+        #[cfg(not(feature = "embedded"))]
+        {
+            Self::from_file("dictionaries/alpha_words.txt")
+        }
+    }
+
     fn segments(&self) -> WordListIter {
         WordListIter {
             words: &self.words,
@@ -93,11 +149,12 @@ impl Word {
         sum
     }
 
-    fn new(string: String) -> Self {
+    fn new(string: String, display: String) -> Self {
         Self {
             char_tbl: Self::_char_tbl(&string),
             string_sum: Self::_string_sum(&string),
             string,
+            display,
         }
     }
     
@@ -122,7 +179,8 @@ impl Word {
 }
 
 fn lines_from_file(filename: impl AsRef<Path>) -> Vec<String> {
-    let file = File::open(filename).expect("no such file");
+    let path = filename.as_ref();
+    let file = File::open(path).unwrap_or_else(|e| panic!("no such file: {} ({})", path.display(), e));
     let buf = BufReader::new(file);
     buf.lines()
         .map(|l| l.expect("Could not parse line"))
@@ -145,8 +203,51 @@ fn progress(done: u32, total: u32) -> String {
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|a| a == "--phrase") {
+        let phrase = args.get(pos + 1).expect("--phrase requires an argument following it");
+
+        let targets: std::collections::HashSet<String> = args
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| a.as_str() == "--hash")
+            .filter_map(|(i, _)| args.get(i + 1))
+            .map(|hash| hash.to_lowercase())
+            .collect();
+
+        if targets.is_empty() {
+            run_phrase_solver(phrase);
+        } else {
+            run_phrase_solver_hashed(phrase, &targets);
+        }
+        return;
+    }
+
+    if let Some(pos) = args.iter().position(|a| a == "--segment") {
+        let input = args.get(pos + 1).expect("--segment requires an argument following it");
+        let freq_path = args
+            .iter()
+            .position(|a| a == "--freq")
+            .and_then(|i| args.get(i + 1))
+            .map(String::as_str)
+            .unwrap_or("dictionaries/unigram_freq.txt");
+        run_segmenter(input, freq_path);
+        return;
+    }
+
+    if let Some(pos) = args.iter().position(|a| a == "--rack") {
+        let rack = args.get(pos + 1).expect("--rack requires an argument following it");
+        let budget: usize = args
+            .iter()
+            .position(|a| a == "--fuzzy")
+            .map(|i| args.get(i + 1).and_then(|v| v.parse().ok()).unwrap_or(1))
+            .unwrap_or(0);
+        run_rack_lookup(rack, budget);
+        return;
+    }
+
     println!("Reading and preprocessing vocabulary...");
-    let vocabulary = WordList::from_file("alpha_words.txt");
+    let vocabulary = WordList::load_default();
     let mut stdout = stdout();
     let mut done = 0;
     let total = vocabulary.len();
@@ -196,7 +297,7 @@ fn main() {
                 done += anagrams.len() as u32;
                 segment.drain_filter(|w| w.is_none());
 
-                groups.insert(&word.string, anagrams);
+                groups.insert(&word.display, anagrams);
             }
         }
     }
@@ -211,7 +312,7 @@ fn main() {
         let mut buf = String::new();
         buf.push_str(&format!("{}:\n", word));
         for word in anagrams.into_iter() {
-            buf.push_str(&format!("   - {}\n", word.string))
+            buf.push_str(&format!("   - {}\n", word.display))
         }
         buf.push_str("------------\n");
         file_buf.push(buf);
@@ -223,3 +324,84 @@ fn main() {
     }
     println!("Done! <3");
 }
+
+fn run_phrase_solver(phrase: &str) {
+    println!("Reading and preprocessing vocabulary...");
+    let vocabulary = WordList::load_default();
+    println!("Using vocabulary of {} words.", vocabulary.len());
+    println!("Searching for phrase anagrams of \"{}\"...", phrase);
+
+    let begin = Instant::now();
+    let solver = phrase::PhraseSolver::new(&vocabulary);
+    let solutions = solver.solve(phrase);
+    let elapsed = begin.elapsed().as_millis();
+
+    println!("Found {} solutions in {}ms.", solutions.len(), elapsed);
+    for solution in &solutions {
+        println!("{}", solution);
+    }
+}
+
+fn run_phrase_solver_hashed(phrase: &str, targets: &std::collections::HashSet<String>) {
+    println!("Reading and preprocessing vocabulary...");
+    let vocabulary = WordList::load_default();
+    println!("Using vocabulary of {} words.", vocabulary.len());
+    println!(
+        "Searching for phrase anagrams of \"{}\" matching {} target hash(es)...",
+        phrase,
+        targets.len()
+    );
+
+    let begin = Instant::now();
+    let solver = phrase::PhraseSolver::new(&vocabulary);
+    let solutions = solver.solve_with_targets(phrase, targets);
+    let elapsed = begin.elapsed().as_millis();
+
+    println!("Found {} matching solutions in {}ms.", solutions.len(), elapsed);
+    for solution in &solutions {
+        println!("{}", solution);
+    }
+}
+
+// Loads a "word count" per line unigram frequency table.
+fn load_unigram_counts(path: &str) -> HashMap<String, u64> {
+    lines_from_file(path)
+        .into_iter()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let word = parts.next()?.to_lowercase();
+            let count = parts.next()?.parse().ok()?;
+            Some((word, count))
+        })
+        .collect()
+}
+
+fn run_segmenter(input: &str, freq_path: &str) {
+    println!("Loading unigram frequency table from {}...", freq_path);
+    let counts = load_unigram_counts(freq_path);
+    let segmenter = segment::Segmenter::from_unigrams(&counts, 20);
+
+    println!("Segmenting \"{}\"...", input);
+    let words = segmenter.segment(input);
+    println!("{}", words.join(" "));
+}
+
+fn run_rack_lookup(rack: &str, budget: usize) {
+    println!("Reading and preprocessing vocabulary...");
+    let vocabulary = WordList::load_default();
+    println!("Using vocabulary of {} words.", vocabulary.len());
+
+    let index = trie::SignatureTrie::build(&vocabulary);
+    let words = if budget > 0 {
+        println!("Looking up words within {} letter(s) of rack \"{}\"...", budget, rack);
+        index.fuzzy_from_rack(rack, budget)
+    } else {
+        println!("Looking up words buildable from rack \"{}\"...", rack);
+        index.words_from_rack(rack)
+    };
+
+    println!("Found {} word(s).", words.len());
+    for word in &words {
+        println!("{}", word);
+    }
+}
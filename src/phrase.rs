@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use core_simd::*;
+use md5::{Digest, Md5};
+use unidecode::unidecode;
+
+use crate::{Word, WordList};
+
+// Normalizes like `WordList::from_file` does, then strips non-letters so
+// spaces and punctuation never reach the letter count table.
+fn normalize_phrase(phrase: &str) -> String {
+    unidecode(phrase)
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect()
+}
+
+fn char_tbl(string: &str) -> [i8; 256] {
+    let mut table = [0i8; 256];
+    for ch in string.as_bytes() {
+        table[*ch as usize] += 1;
+    }
+    table
+}
+
+fn fits(remaining: &[i8; 256], word: &[i8; 256]) -> bool {
+    for i in 0..4 {
+        let rem = i8x64::from_slice(&remaining[i * 64..i * 64 + 64]);
+        let w = i8x64::from_slice(&word[i * 64..i * 64 + 64]);
+        if !(rem - w).lanes_ge(i8x64::splat(0)).all() {
+            return false;
+        }
+    }
+    true
+}
+
+fn subtract(remaining: &[i8; 256], word: &[i8; 256]) -> [i8; 256] {
+    let mut out = [0i8; 256];
+    for i in 0..4 {
+        let rem = i8x64::from_slice(&remaining[i * 64..i * 64 + 64]);
+        let w = i8x64::from_slice(&word[i * 64..i * 64 + 64]);
+        (rem - w).copy_to_slice(&mut out[i * 64..i * 64 + 64]);
+    }
+    out
+}
+
+fn is_zero(table: &[i8; 256]) -> bool {
+    table.iter().all(|&c| c == 0)
+}
+
+pub(crate) struct PhraseSolver<'a> {
+    words: &'a [Word],
+}
+
+impl<'a> PhraseSolver<'a> {
+    pub(crate) fn new(list: &'a WordList) -> Self {
+        Self { words: &list.words }
+    }
+
+    pub(crate) fn solve(&self, phrase: &str) -> Vec<String> {
+        let target = char_tbl(&normalize_phrase(phrase));
+        let candidates: Vec<&Word> = self.words.iter().collect();
+
+        let mut solutions = Vec::new();
+        let mut chosen = Vec::new();
+        self.recurse(&target, &candidates, &mut chosen, &mut solutions);
+        solutions
+    }
+
+    fn recurse<'w>(
+        &self,
+        remaining: &[i8; 256],
+        candidates: &[&'w Word],
+        chosen: &mut Vec<&'w Word>,
+        solutions: &mut Vec<String>,
+    ) {
+        if is_zero(remaining) {
+            solutions.push(
+                chosen
+                    .iter()
+                    .map(|w| w.display.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+            return;
+        }
+
+        // Only words at or past the last chosen index are considered, so a
+        // multiset of words is never emitted in more than one order. Filtering
+        // once per depth keeps sibling branches from rescanning the dictionary.
+        let fitting: Vec<&'w Word> = candidates
+            .iter()
+            .copied()
+            .filter(|w| fits(remaining, &w.char_tbl))
+            .collect();
+
+        for (i, word) in fitting.iter().enumerate() {
+            let next_remaining = subtract(remaining, &word.char_tbl);
+            chosen.push(word);
+            self.recurse(&next_remaining, &fitting[i..], chosen, solutions);
+            chosen.pop();
+        }
+    }
+
+    /// Like [`solve`](Self::solve), but only reports phrases whose MD5 digest
+    /// is one of `targets`. The digest is computed over the canonical byte
+    /// form: the chosen words' normalized (lowercase, transliterated)
+    /// spellings joined with single spaces, regardless of how the phrase
+    /// argument or the dictionary's display spellings were written; that form
+    /// with one trailing space appended is hashed and matched as well. Targets
+    /// are 32 lowercase hex digest strings. Search stops as soon as every
+    /// target has been matched, since the candidate space can run into the
+    /// millions of phrases long before the dictionary is exhausted.
+    pub(crate) fn solve_with_targets(&self, phrase: &str, targets: &HashSet<String>) -> Vec<String> {
+        let target = char_tbl(&normalize_phrase(phrase));
+        let candidates: Vec<&Word> = self.words.iter().collect();
+
+        let mut solutions = Vec::new();
+        let mut chosen = Vec::new();
+        let mut remaining_targets = targets.clone();
+        self.recurse_hashed(
+            &target,
+            &candidates,
+            &mut chosen,
+            &mut solutions,
+            &mut remaining_targets,
+        );
+        solutions
+    }
+
+    fn recurse_hashed<'w>(
+        &self,
+        remaining: &[i8; 256],
+        candidates: &[&'w Word],
+        chosen: &mut Vec<&'w Word>,
+        solutions: &mut Vec<String>,
+        remaining_targets: &mut HashSet<String>,
+    ) {
+        if remaining_targets.is_empty() {
+            return;
+        }
+
+        if is_zero(remaining) {
+            let canonical = chosen
+                .iter()
+                .map(|w| w.string.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            // A hash may have been taken over a trailing space as well as the
+            // single-interior-space form, so both are tried.
+            let with_trailing_space = format!("{} ", canonical);
+
+            let digest = format!("{:x}", Md5::digest(canonical.as_bytes()));
+            let digest_trailing = format!("{:x}", Md5::digest(with_trailing_space.as_bytes()));
+
+            if remaining_targets.remove(&digest) || remaining_targets.remove(&digest_trailing) {
+                let display = chosen
+                    .iter()
+                    .map(|w| w.display.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                solutions.push(display);
+            }
+            return;
+        }
+
+        let fitting: Vec<&'w Word> = candidates
+            .iter()
+            .copied()
+            .filter(|w| fits(remaining, &w.char_tbl))
+            .collect();
+
+        for (i, word) in fitting.iter().enumerate() {
+            if remaining_targets.is_empty() {
+                break;
+            }
+            let next_remaining = subtract(remaining, &word.char_tbl);
+            chosen.push(word);
+            self.recurse_hashed(&next_remaining, &fitting[i..], chosen, solutions, remaining_targets);
+            chosen.pop();
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+pub(crate) struct Segmenter {
+    unigram_log_probs: HashMap<String, f64>,
+    bigram_log_probs: HashMap<(String, String), f64>,
+    unknown_log_prob: f64,
+    max_word_len: usize,
+}
+
+impl Segmenter {
+    pub(crate) fn from_unigrams(counts: &HashMap<String, u64>, max_word_len: usize) -> Self {
+        let total: u64 = counts.values().sum();
+
+        let unigram_log_probs = counts
+            .iter()
+            .map(|(word, &count)| (word.clone(), (count as f64 / total as f64).ln()))
+            .collect();
+
+        Self {
+            unigram_log_probs,
+            bigram_log_probs: HashMap::new(),
+            unknown_log_prob: (1.0 / total as f64).ln(),
+            max_word_len,
+        }
+    }
+
+    pub(crate) fn with_bigrams(mut self, counts: &HashMap<(String, String), u64>) -> Self {
+        let total: u64 = counts.values().sum();
+
+        self.bigram_log_probs = counts
+            .iter()
+            .map(|(pair, &count)| (pair.clone(), (count as f64 / total as f64).ln()))
+            .collect();
+
+        self
+    }
+
+    fn logprob(&self, word: &str, previous: Option<&str>) -> f64 {
+        if let Some(previous) = previous {
+            if let Some(&p) = self
+                .bigram_log_probs
+                .get(&(previous.to_string(), word.to_string()))
+            {
+                return p;
+            }
+        }
+
+        self.unigram_log_probs
+            .get(word)
+            .copied()
+            // Unknown substrings get a small per-character penalty so garbage
+            // input still terminates instead of winning by default.
+            .unwrap_or(self.unknown_log_prob * word.len() as f64)
+    }
+
+    pub(crate) fn segment(&self, input: &str) -> Vec<String> {
+        let chars: Vec<char> = input.chars().collect();
+        let n = chars.len();
+
+        let mut best = vec![f64::NEG_INFINITY; n + 1];
+        let mut back = vec![0usize; n + 1];
+        best[0] = 0.0;
+
+        for i in 1..=n {
+            let lower = i.saturating_sub(self.max_word_len);
+            for j in lower..i {
+                if best[j] == f64::NEG_INFINITY {
+                    continue;
+                }
+
+                let word: String = chars[j..i].iter().collect();
+                let previous = if j == 0 {
+                    None
+                } else {
+                    Some(chars[back[j]..j].iter().collect::<String>())
+                };
+
+                let score = best[j] + self.logprob(&word, previous.as_deref());
+                if score > best[i] {
+                    best[i] = score;
+                    back[i] = j;
+                }
+            }
+        }
+
+        let mut words = Vec::new();
+        let mut i = n;
+        while i > 0 {
+            let j = back[i];
+            words.push(chars[j..i].iter().collect::<String>());
+            i = j;
+        }
+        words.reverse();
+        words
+    }
+}
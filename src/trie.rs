@@ -0,0 +1,80 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::WordList;
+
+// Keyed by the next letter of a word's sorted signature; `words` holds
+// every word whose signature ends at this node.
+#[derive(Default)]
+struct TrieNode {
+    children: HashMap<char, Box<TrieNode>>,
+    words: Vec<String>,
+}
+
+pub(crate) struct SignatureTrie {
+    root: TrieNode,
+}
+
+impl SignatureTrie {
+    pub(crate) fn build(list: &WordList) -> Self {
+        let mut root = TrieNode::default();
+
+        for word in &list.words {
+            let mut signature: Vec<char> = word.string.chars().collect();
+            signature.sort_unstable();
+
+            let mut node = &mut root;
+            for ch in signature {
+                node = node.children.entry(ch).or_insert_with(|| Box::new(TrieNode::default()));
+            }
+            node.words.push(word.display.clone());
+        }
+
+        Self { root }
+    }
+
+    pub(crate) fn words_from_rack(&self, rack: &str) -> Vec<String> {
+        let mut rack_counts = letter_counts(rack);
+        let mut results = Vec::new();
+        Self::descend(&self.root, &mut rack_counts, 0, &mut results);
+        dedupe(results)
+    }
+
+    fn descend(node: &TrieNode, rack_counts: &mut HashMap<char, i32>, budget: usize, results: &mut Vec<String>) {
+        results.extend(node.words.iter().cloned());
+
+        for (&ch, child) in node.children.iter() {
+            let available = rack_counts.get(&ch).copied().unwrap_or(0);
+            if available > 0 {
+                *rack_counts.get_mut(&ch).unwrap() -= 1;
+                Self::descend(child, rack_counts, budget, results);
+                *rack_counts.get_mut(&ch).unwrap() += 1;
+            } else if budget > 0 {
+                // Spend one unit of budget substituting for a letter the
+                // rack doesn't have, reaching words that differ by it.
+                Self::descend(child, rack_counts, budget - 1, results);
+            }
+        }
+    }
+
+    // Like words_from_rack, but allows up to `budget` substituted letters.
+    pub(crate) fn fuzzy_from_rack(&self, rack: &str, budget: usize) -> Vec<String> {
+        let mut rack_counts = letter_counts(rack);
+        let mut results = Vec::new();
+        Self::descend(&self.root, &mut rack_counts, budget, &mut results);
+        dedupe(results)
+    }
+}
+
+fn dedupe(mut words: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    words.retain(|word| seen.insert(word.clone()));
+    words
+}
+
+fn letter_counts(rack: &str) -> HashMap<char, i32> {
+    let mut counts = HashMap::new();
+    for ch in rack.chars() {
+        *counts.entry(ch).or_insert(0) += 1;
+    }
+    counts
+}